@@ -0,0 +1,239 @@
+/*
+ * Copyright (C) 2023 EverX. All Rights Reserved.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ */
+
+use super::parser::ParsedItem;
+use super::Result;
+use crate::debug::DbgNode;
+use crate::fail;
+use crate::writer::Units;
+use std::collections::HashMap;
+use tycho_types::prelude::CellBuilder;
+
+/// Encodes a fixed-arity opcode with no nested continuations, e.g. `ADD` or `DUP`.
+type PlainEncoder = fn(&[String]) -> Result<Vec<u8>>;
+
+/// Encodes an opcode that carries one or more nested continuations (`PUSHCONT`, `IFELSE`, ...),
+/// returning the raw command bytes that precede the continuation references.
+type CompositeEncoder = fn(&[String], usize) -> Result<Vec<u8>>;
+
+/// Mirrors the disassembler's opcode table (see `disasm::handlers`), but in the opposite
+/// direction: mnemonic -> encoder, instead of opcode bits -> `Instruction`. Extending assembler
+/// coverage means adding an entry here the same way a new disasm handler is registered there.
+/// Covers the common stack/arithmetic/logic/comparison primitives plus a handful of control-flow
+/// opcodes; still far short of every opcode `disasm::handlers` recognizes, so an unfamiliar
+/// mnemonic in a real listing is expected to need a new entry here rather than indicate a bug.
+///
+/// Known gaps, roughly in the order a real contract is likely to hit them:
+/// - dictionary/cell primitives (`DICTPUSHCONST`, `DICTUGETJMP[Z]`, `PFXDICTSWITCH`, `STDICT`, ...)
+/// - full-width stack ops beyond the `N2` pairs already here (`PUSH n`, `POP n`, `XCHG n`, ...)
+/// - exception handling beyond `THROWIFNOT` (`THROW`, `THROWIF`, `TRY`, ...)
+/// - the `c4`/`c5`/`c7` register ops real selectors start with (`PUSHCTR`, `POPCTR`, ...)
+/// - full-width `PUSHINT`/signed big-int encodings beyond the short form and the 32-bit fallback
+///   `encode_pushint` already has
+fn plain_table() -> HashMap<&'static str, PlainEncoder> {
+    let mut table: HashMap<&'static str, PlainEncoder> = HashMap::new();
+    // stack manipulation
+    table.insert("NOP", |_| Ok(vec![0x00]));
+    table.insert("DUP", |_| Ok(vec![0x20]));
+    table.insert("OVER", |_| Ok(vec![0x21]));
+    table.insert("SWAP", |_| Ok(vec![0x01]));
+    table.insert("DROP", |_| Ok(vec![0x30]));
+    table.insert("DROP2", |_| Ok(vec![0x5B]));
+    table.insert("DUP2", |_| Ok(vec![0x5C]));
+    table.insert("OVER2", |_| Ok(vec![0x5D]));
+    table.insert("SWAP2", |_| Ok(vec![0x5A]));
+    table.insert("ROT", |_| Ok(vec![0x58]));
+    table.insert("ROTREV", |_| Ok(vec![0x59]));
+    // arithmetic
+    table.insert("ADD", |_| Ok(vec![0xA0]));
+    table.insert("SUB", |_| Ok(vec![0xA1]));
+    table.insert("NEGATE", |_| Ok(vec![0xA3]));
+    table.insert("INC", |_| Ok(vec![0xA4]));
+    table.insert("DEC", |_| Ok(vec![0xA5]));
+    table.insert("MUL", |_| Ok(vec![0xA8]));
+    table.insert("DIV", |_| Ok(vec![0xA9, 0x04]));
+    // bitwise logic
+    table.insert("AND", |_| Ok(vec![0xB0]));
+    table.insert("OR", |_| Ok(vec![0xB1]));
+    table.insert("XOR", |_| Ok(vec![0xB2]));
+    table.insert("NOT", |_| Ok(vec![0xB3]));
+    // comparison
+    table.insert("SGN", |_| Ok(vec![0xB8]));
+    table.insert("LESS", |_| Ok(vec![0xB9]));
+    table.insert("EQUAL", |_| Ok(vec![0xBA]));
+    table.insert("LEQ", |_| Ok(vec![0xBB]));
+    table.insert("GREATER", |_| Ok(vec![0xBC]));
+    table.insert("NEQ", |_| Ok(vec![0xBD]));
+    table.insert("GEQ", |_| Ok(vec![0xBE]));
+    table.insert("CMP", |_| Ok(vec![0xBF]));
+    // control flow
+    table.insert("RET", |_| Ok(vec![0xDB, 0x30]));
+    table.insert("THROWIFNOT", encode_throwifnot);
+    table.insert("PUSHINT", encode_pushint);
+    table.insert("PUSHREF", encode_pushref);
+    table
+}
+
+fn composite_table() -> HashMap<&'static str, CompositeEncoder> {
+    let mut table: HashMap<&'static str, CompositeEncoder> = HashMap::new();
+    table.insert("PUSHCONT", |_, n| {
+        if n != 1 {
+            fail!("PUSHCONT takes exactly one continuation");
+        }
+        Ok(vec![0x8E])
+    });
+    table.insert("IF", |_, n| {
+        if n != 1 {
+            fail!("IF takes exactly one continuation");
+        }
+        Ok(vec![0xE0])
+    });
+    table.insert("IFNOT", |_, n| {
+        if n != 1 {
+            fail!("IFNOT takes exactly one continuation");
+        }
+        Ok(vec![0xE1])
+    });
+    table.insert("IFELSE", |_, n| {
+        if n != 2 {
+            fail!("IFELSE takes exactly two continuations");
+        }
+        Ok(vec![0xE2])
+    });
+    table
+}
+
+fn encode_throwifnot(args: &[String]) -> Result<Vec<u8>> {
+    let n: u32 = parse_arg(args, 0, "THROWIFNOT")?;
+    if n >= 64 {
+        fail!("THROWIFNOT argument out of range: {}", n);
+    }
+    Ok(vec![0xF2, 0x00 | n as u8])
+}
+
+fn encode_pushint(args: &[String]) -> Result<Vec<u8>> {
+    let value: i32 = parse_arg(args, 0, "PUSHINT")?;
+    if (-5..=10).contains(&value) {
+        // short form: tiny integers are folded into a single byte, opcode nibble `i = x + 5`
+        // (so `0x70` is -5, `0x75` is 0, `0x7A` is 5, `0x7F` is 10), same range and encoding the
+        // disassembler's handler for this opcode recognizes
+        return Ok(vec![0x70 | (value + 5) as u8]);
+    }
+    let mut bytes = vec![0x80];
+    bytes.extend_from_slice(&value.to_be_bytes());
+    Ok(bytes)
+}
+
+fn encode_pushref(args: &[String]) -> Result<Vec<u8>> {
+    if !args.is_empty() {
+        fail!("PUSHREF takes no arguments");
+    }
+    Ok(vec![0x88])
+}
+
+fn parse_arg<T: std::str::FromStr>(args: &[String], index: usize, mnemonic: &str) -> Result<T> {
+    args.get(index)
+        .ok_or_else(|| anyhow::anyhow!("{} is missing argument {}", mnemonic, index))?
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{} has a malformed argument {}", mnemonic, index))
+}
+
+/// Drives `units` through the assembler table for every parsed item, in order.
+pub fn encode(items: &[ParsedItem], units: &mut Units) -> Result<()> {
+    let plain = plain_table();
+    let composite = composite_table();
+    for item in items {
+        match item {
+            ParsedItem::Instruction { mnemonic, args } => {
+                let encoder = plain
+                    .get(mnemonic.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("unknown opcode `{}`", mnemonic))?;
+                let bytes = encoder(args)?;
+                units.write_command(&bytes, DbgNode::default())?;
+            }
+            ParsedItem::Composite {
+                mnemonic,
+                args,
+                bodies,
+            } => {
+                let encoder = composite
+                    .get(mnemonic.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("unknown composite opcode `{}`", mnemonic))?;
+                let prefix = encoder(args, bodies.len())?;
+                let mut dbg = DbgNode::default();
+                let mut references = Vec::with_capacity(bodies.len());
+                for body in bodies {
+                    let (builder, child) = encode_to_builder(body)?;
+                    references.push(builder);
+                    dbg.children.push(child);
+                }
+                units.write_composite_command(&prefix, references, dbg)?;
+            }
+            ParsedItem::Blob(hex) => {
+                let (bytes, bits) = decode_blob_hex(hex)?;
+                units.write_command_bitstring(&bytes, bits, DbgNode::default())?;
+            }
+            ParsedItem::Cell(body) => {
+                let (builder, child) = encode_to_builder(body)?;
+                let mut dbg = DbgNode::default();
+                dbg.children.push(child);
+                units.write_composite_command(&[], vec![builder], dbg)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encodes a nested block (a continuation's body, or a `.cell { ... }`) into its own cell,
+/// together with that cell's debug node.
+fn encode_to_builder(items: &[ParsedItem]) -> Result<(CellBuilder, DbgNode)> {
+    let mut units = Units::new();
+    encode(items, &mut units)?;
+    Ok(units.finalize())
+}
+
+fn pad_even(hex: &str) -> String {
+    if hex.len() % 2 == 0 {
+        hex.to_owned()
+    } else {
+        format!("{}0", hex)
+    }
+}
+
+/// Decodes a `.blob` hex string, honoring the completion-tag convention `display_data()` (used
+/// throughout `Code::print`/`codedict.rs`) emits for a bitstring that isn't a whole number of
+/// bytes: a trailing `_` means the true bit count is recovered from the trailing byte, whose
+/// lowest set bit is the tag (a single `1` followed by zero padding out to the byte boundary), not
+/// data. Returns the raw bytes together with the bit count to actually write.
+fn decode_blob_hex(hex: &str) -> Result<(Vec<u8>, usize)> {
+    match hex.strip_suffix('_') {
+        Some(rest) => {
+            let bytes = hex::decode(pad_even(rest))
+                .map_err(|e| anyhow::anyhow!("malformed `.blob` hex string: {}", e))?;
+            let last = *bytes
+                .last()
+                .ok_or_else(|| anyhow::anyhow!("empty completion-tagged `.blob`"))?;
+            if last == 0 {
+                fail!("malformed `.blob` completion tag: trailing byte has no tag bit set");
+            }
+            let bits = bytes.len() * 8 - last.trailing_zeros() as usize - 1;
+            Ok((bytes, bits))
+        }
+        None => {
+            let bytes = hex::decode(pad_even(hex))
+                .map_err(|e| anyhow::anyhow!("malformed `.blob` hex string: {}", e))?;
+            let bits = bytes.len() * 8;
+            Ok((bytes, bits))
+        }
+    }
+}
@@ -0,0 +1,43 @@
+/*
+ * Copyright (C) 2023 EverX. All Rights Reserved.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ */
+
+use crate::debug::DbgInfo;
+use crate::writer::Units;
+use tycho_types::prelude::Cell;
+
+pub mod encoder;
+pub mod parser;
+#[cfg(test)]
+mod tests;
+
+pub type Result<T> = std::result::Result<T, anyhow::Error>;
+
+/// Assembles a textual listing (as emitted by [`crate::disasm::disasm`]/[`crate::disasm::disasm_ex`])
+/// back into a cell tree, producing the same `(Cell, DbgInfo)` shape the compiler does.
+///
+/// This is the inverse of the disassembler: [`parser::parse`] turns the listing into a tree of
+/// [`parser::ParsedItem`]s, [`encoder::encode`] drives a [`Units`] builder through the assembler
+/// table to emit each opcode (and recurse into nested continuations / referenced cells), and
+/// [`Units::finalize`] collapses the result into a single root cell plus its debug map.
+///
+/// The parser keeps no line numbers, so every instruction is encoded with an empty
+/// [`DbgNode`](crate::debug::DbgNode) and the returned [`DbgInfo`] carries no source locations.
+pub fn assemble(text: &str) -> Result<(Cell, DbgInfo)> {
+    let items = parser::parse(text)?;
+    let mut units = Units::new();
+    encoder::encode(&items, &mut units)?;
+    let (builder, dbg) = units.finalize();
+    let cell = builder.build()?;
+    let dbg_info = DbgInfo::from(cell.clone(), dbg);
+    Ok((cell, dbg_info))
+}
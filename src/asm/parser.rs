@@ -0,0 +1,127 @@
+/*
+ * Copyright (C) 2023 EverX. All Rights Reserved.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ */
+
+use super::Result;
+use crate::fail;
+
+/// A single parsed line (or block) of an assembler listing.
+#[derive(Debug, Clone)]
+pub enum ParsedItem {
+    /// A plain opcode line, e.g. `PUSHINT 10`.
+    Instruction {
+        mnemonic: String,
+        args: Vec<String>,
+    },
+    /// An opcode line followed by one or more brace-delimited continuations, e.g. `PUSHCONT {`
+    /// or the two branches of `IFELSE { ... } { ... }`.
+    Composite {
+        mnemonic: String,
+        args: Vec<String>,
+        bodies: Vec<Vec<ParsedItem>>,
+    },
+    /// A raw bitstring emitted verbatim by the disassembler as `.blob xHEX`.
+    Blob(String),
+    /// An opaque referenced cell printed as `.cell { ... }` (data the disassembler could not
+    /// decode as code).
+    Cell(Vec<ParsedItem>),
+}
+
+struct Lines<'a> {
+    tokens: std::iter::Peekable<std::slice::Iter<'a, &'a str>>,
+}
+
+impl<'a> Lines<'a> {
+    fn next_line(&mut self) -> Option<&'a str> {
+        self.tokens.next().copied()
+    }
+    fn peek_line(&mut self) -> Option<&&'a str> {
+        self.tokens.peek().copied()
+    }
+}
+
+/// Parses a textual listing (as produced by `Code::print`) into a tree of [`ParsedItem`]s.
+pub fn parse(text: &str) -> Result<Vec<ParsedItem>> {
+    let raw_lines: Vec<&str> = text.lines().collect();
+    let mut lines = Lines {
+        tokens: raw_lines.iter().peekable(),
+    };
+    let items = parse_block(&mut lines)?;
+    Ok(items)
+}
+
+/// Strips a trailing `;; ...` comment (as emitted after `.cell {` with the cell's `repr_hash`)
+/// from a line, same as whole-line comments are ignored in [`parse_block`].
+fn strip_trailing_comment(line: &str) -> &str {
+    match line.find(";;") {
+        Some(pos) => line[..pos].trim(),
+        None => line,
+    }
+}
+
+fn parse_block(lines: &mut Lines) -> Result<Vec<ParsedItem>> {
+    let mut items = Vec::new();
+    while let Some(raw) = lines.peek_line() {
+        let trimmed = raw.trim();
+        if trimmed == "}" {
+            lines.next_line();
+            return Ok(items);
+        }
+        if trimmed == "} {" {
+            // closes this block and opens the next branch of the same instruction; left for the
+            // caller to consume
+            return Ok(items);
+        }
+        lines.next_line();
+        if trimmed.is_empty() || trimmed.starts_with(";;") {
+            continue;
+        }
+        if let Some(hex) = trimmed.strip_prefix(".blob x") {
+            items.push(ParsedItem::Blob(hex.trim().to_owned()));
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix(".cell") {
+            let rest = strip_trailing_comment(rest.trim());
+            if rest != "{" {
+                fail!("malformed `.cell` block: expected `{{`, got `{}`", trimmed);
+            }
+            let body = parse_block(lines)?;
+            items.push(ParsedItem::Cell(body));
+            continue;
+        }
+        let mut parts = trimmed.split_whitespace();
+        let mnemonic = parts
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("empty instruction line"))?
+            .to_owned();
+        let mut args: Vec<String> = parts.map(|s| s.to_owned()).collect();
+        if args.last().map(|s| s.as_str()) == Some("{") {
+            args.pop();
+            let mut bodies = vec![parse_block(lines)?];
+            // instructions with more than one continuation (e.g. `IFELSE`) print each
+            // extra branch as its own brace block immediately following the previous one, opened
+            // either on its own line or on the previous block's closing line (`} {`)
+            while matches!(lines.peek_line().map(|l| l.trim()), Some("{") | Some("} {")) {
+                lines.next_line();
+                bodies.push(parse_block(lines)?);
+            }
+            items.push(ParsedItem::Composite {
+                mnemonic,
+                args,
+                bodies,
+            });
+        } else {
+            items.push(ParsedItem::Instruction { mnemonic, args });
+        }
+    }
+    Ok(items)
+}
@@ -0,0 +1,120 @@
+/*
+ * Copyright (C) 2023 EverX. All Rights Reserved.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ */
+
+use super::encoder;
+use super::parser::{self, ParsedItem};
+use crate::writer::Units;
+
+#[test]
+fn parses_cell_block_with_trailing_hash_comment() {
+    // the disassembler always prints the opening brace of a `.cell` block on the same line as
+    // its `;; #<repr_hash>` comment; a regression here breaks every round trip.
+    let listing = ".cell { ;; #deadbeef\n.blob x00\n}\n";
+    let items = parser::parse(listing).unwrap();
+    assert_eq!(items.len(), 1);
+    match &items[0] {
+        ParsedItem::Cell(body) => assert_eq!(body.len(), 1),
+        other => panic!("expected a Cell block, got {:?}", other),
+    }
+}
+
+#[test]
+fn rejects_malformed_cell_header() {
+    let err = parser::parse(".cell garbage\n").unwrap_err();
+    assert!(err.to_string().contains("malformed"));
+}
+
+#[test]
+fn parses_composite_with_multiple_bodies() {
+    let listing = "IFELSE {\nADD\n} {\nSUB\n}\n";
+    let items = parser::parse(listing).unwrap();
+    match &items[0] {
+        ParsedItem::Composite {
+            mnemonic, bodies, ..
+        } => {
+            assert_eq!(mnemonic, "IFELSE");
+            assert_eq!(bodies.len(), 2);
+        }
+        other => panic!("expected a Composite item, got {:?}", other),
+    }
+}
+
+#[test]
+fn encodes_every_plain_and_composite_mnemonic() {
+    let listing = "\
+NOP\nDUP\nOVER\nSWAP\nDROP\nDROP2\nDUP2\nOVER2\nSWAP2\nROT\nROTREV\n\
+ADD\nSUB\nNEGATE\nINC\nDEC\nMUL\nDIV\n\
+AND\nOR\nXOR\nNOT\n\
+SGN\nLESS\nEQUAL\nLEQ\nGREATER\nNEQ\nGEQ\nCMP\n\
+RET\nTHROWIFNOT 3\nPUSHINT 7\nPUSHINT 1000\nPUSHREF\n\
+PUSHCONT {\nNOP\n}\nIF {\nNOP\n}\nIFNOT {\nNOP\n}\nIFELSE {\nNOP\n} {\nNOP\n}\n";
+    let items = parser::parse(listing).unwrap();
+    let mut units = Units::new();
+    encoder::encode(&items, &mut units).unwrap();
+}
+
+#[test]
+fn assembles_a_full_listing_through_the_public_entry_point() {
+    // Exercises `assemble` itself (parse -> encode -> finalize -> build), not just the parser
+    // and encoder in isolation: a listing with nested continuations, a referenced cell, and a
+    // raw blob, taken through the crate's public `assemble` function. The result is not
+    // disassembled again; the test checks only that a non-empty cell comes out.
+    let listing = "\
+PUSHINT 1\nIFELSE {\nPUSHINT 2\n} {\nPUSHINT 3\n}\n\
+.cell {\n.blob x00\n}\n\
+PUSHREF\n";
+    let (cell, _dbg) = super::assemble(listing).unwrap();
+    assert!(cell.as_slice().unwrap().size_bits() > 0);
+}
+
+#[test]
+fn encodes_pushint_short_form_bytes() {
+    // `x = i - 5`: -5 -> 0x70, 0 -> 0x75, 7 -> 0x7C, 10 -> 0x7F. A regression here silently
+    // corrupts every tiny-int PUSHINT in a round trip without failing to parse or encode.
+    let listing = "PUSHINT -5\nPUSHINT 0\nPUSHINT 7\nPUSHINT 10\n";
+    let items = parser::parse(listing).unwrap();
+    let mut units = Units::new();
+    encoder::encode(&items, &mut units).unwrap();
+    let (builder, _dbg) = units.finalize();
+    let cell = builder.build().unwrap();
+    assert_eq!(cell.as_slice().unwrap().display_data(), "70757c7f");
+}
+
+#[test]
+fn round_trips_non_byte_aligned_blob_via_completion_tag() {
+    // 4 bits of data (`1100`) rendered with a byte-level completion tag is `c8_`: the tag bit
+    // plus zero padding fill the rest of the byte (`1100` + `1` + `000` = `0xC8`).
+    let listing = ".blob xc8_\n";
+    let items = parser::parse(listing).unwrap();
+    let mut units = Units::new();
+    encoder::encode(&items, &mut units).unwrap();
+    let (builder, _dbg) = units.finalize();
+    let cell = builder.build().unwrap();
+    assert_eq!(cell.as_slice().unwrap().display_data(), "c8_");
+}
+
+#[test]
+fn rejects_unknown_mnemonic() {
+    let items = parser::parse("NOTAREALOPCODE\n").unwrap();
+    let mut units = Units::new();
+    let err = encoder::encode(&items, &mut units).unwrap_err();
+    assert!(err.to_string().contains("unknown opcode"));
+}
+
+#[test]
+fn rejects_ifelse_with_wrong_number_of_bodies() {
+    let items = parser::parse("IFELSE {\nNOP\n}\n").unwrap();
+    let mut units = Units::new();
+    let err = encoder::encode(&items, &mut units).unwrap_err();
+    assert!(err.to_string().contains("IFELSE"));
+}
@@ -12,13 +12,14 @@
  */
 
 use clap::{Parser, Subcommand};
+use tsol_asm::asm::assemble;
+use tsol_asm::boc_io::{resolve_boc_bytes, write_boc, BocFormat};
 use tsol_asm::disasm::{disasm_ex, fmt::print_tree_of_cells, loader::Loader};
 use tsol_asm::Status;
 use tsol_asm::{error, parse_hex_slice};
-use std::{collections::HashSet, io::Write, process::ExitCode};
+use std::{collections::HashSet, process::ExitCode};
 use tycho_types::boc::de::BocHeader;
 use tycho_types::boc::de::Options;
-use tycho_types::boc::Boc;
 use tycho_types::prelude::{Cell, CellFamily};
 
 #[derive(Parser)]
@@ -32,20 +33,26 @@ struct Cli {
 enum Commands {
     /// Dump a boc as a tree of bitstrings
     Dump {
-        /// input boc
+        /// input boc: a file path, a hex string, or a base64 string
         boc: String,
     },
     /// Extract one cell from a boc
     Extract {
         /// cell index (from 0 to 3)
         index: usize,
-        /// input boc
+        /// input boc: a file path, a hex string, or a base64 string
         boc: String,
-        /// output boc
+        /// output boc (pass `-` for stdout)
         output_boc: String,
         /// root index (0 by default)
         #[arg(short, long)]
         root: Option<usize>,
+        /// write the output boc as a hex string instead of raw bytes
+        #[arg(long, conflicts_with = "base64")]
+        hex: bool,
+        /// write the output boc as a base64 string instead of raw bytes
+        #[arg(long, conflicts_with = "hex")]
+        base64: bool,
     },
     /// Disassemble a code fragment
     Fragment {
@@ -54,7 +61,7 @@ enum Commands {
     },
     /// Disassemble a code boc
     Text {
-        /// input boc
+        /// input boc: a file path, a hex string, or a base64 string
         boc: String,
         /// interpret the boc as StateInit and take the code cell
         #[arg(short, long)]
@@ -63,6 +70,17 @@ enum Commands {
         #[arg(short, long)]
         full: bool,
     },
+    /// Assemble a textual listing (as emitted by `Text`/`Fragment`) back into a boc
+    Assemble {
+        /// input assembler listing
+        input: String,
+        /// output boc
+        output_boc: String,
+        /// write the assembler debug map (offset -> source location) to this path as JSON; the
+        /// listing parser keeps no line numbers, so the map carries no source locations
+        #[arg(long)]
+        source_map: Option<String>,
+    },
 }
 
 fn main() -> ExitCode {
@@ -74,6 +92,16 @@ fn main() -> ExitCode {
     }
 }
 
+fn output_format(hex: bool, base64: bool) -> BocFormat {
+    if hex {
+        BocFormat::Hex
+    } else if base64 {
+        BocFormat::Base64
+    } else {
+        BocFormat::Binary
+    }
+}
+
 fn main_impl() -> Status {
     let cli = Cli::parse();
     match cli.command {
@@ -83,20 +111,27 @@ fn main_impl() -> Status {
             output_boc,
             index,
             root,
-        } => subcommand_extract(boc, output_boc, index, root),
+            hex,
+            base64,
+        } => subcommand_extract(boc, output_boc, index, root, output_format(hex, base64)),
         Commands::Fragment { bitstring } => subcommand_fragment(bitstring),
         Commands::Text {
             boc,
             stateinit,
             full,
         } => subcommand_text(boc, stateinit, full),
+        Commands::Assemble {
+            input,
+            output_boc,
+            source_map,
+        } => subcommand_assemble(input, output_boc, source_map),
     }
 }
 
-fn subcommand_dump(filename: String) -> Status {
+fn subcommand_dump(boc: String) -> Status {
     use tycho_types::boc::de::*;
 
-    let tvc = std::fs::read(filename).map_err(|e| error!("failed to read boc file: {}", e))?;
+    let tvc = resolve_boc_bytes(&boc)?;
     let header =
         BocHeader::decode(tvc.as_slice(), &Options::default()).map_err(|e| error!("{}", e))?;
     let roots = header.roots();
@@ -133,12 +168,13 @@ fn count_unique_cells(cell: &Cell) -> usize {
 }
 
 fn subcommand_extract(
-    filename: String,
+    boc: String,
     output: String,
     index: usize,
     root: Option<usize>,
+    format: BocFormat,
 ) -> Status {
-    let boc = std::fs::read(filename).map_err(|e| error!("failed to read input file: {}", e))?;
+    let boc = resolve_boc_bytes(&boc)?;
 
     let header = BocHeader::decode(&boc, &Options::default())?;
     let cells = header.finalize(Cell::empty_context())?;
@@ -152,11 +188,7 @@ fn subcommand_extract(
         .reference_cloned(index as u8)
         .ok_or_else(|| error!("failed to get reference {}", root_index))?;
 
-    let output_bytes = Boc::encode(&cell);
-    let mut output_file = std::fs::File::create(output)?;
-    output_file.write_all(&output_bytes)?;
-
-    Ok(())
+    write_boc(&cell, format, &output)
 }
 
 fn subcommand_fragment(fragment: String) -> Status {
@@ -171,8 +203,8 @@ fn subcommand_fragment(fragment: String) -> Status {
     Ok(())
 }
 
-fn subcommand_text(filename: String, stateinit: bool, full: bool) -> Status {
-    let boc = std::fs::read(filename).map_err(|e| error!("failed to read input file: {}", e))?;
+fn subcommand_text(boc: String, stateinit: bool, full: bool) -> Status {
+    let boc = resolve_boc_bytes(&boc)?;
     let header = BocHeader::decode(&boc, &Options::default())?;
     let roots = header.roots();
     let cells = header.finalize(Cell::empty_context())?;
@@ -198,3 +230,18 @@ fn subcommand_text(filename: String, stateinit: bool, full: bool) -> Status {
     print!("{}", disasm_ex(&mut cell.as_slice().unwrap(), !full)?);
     Ok(())
 }
+
+fn subcommand_assemble(filename: String, output: String, source_map: Option<String>) -> Status {
+    let text =
+        std::fs::read_to_string(filename).map_err(|e| error!("failed to read input file: {}", e))?;
+
+    let (cell, dbg_info) = assemble(&text)?;
+
+    write_boc(&cell, BocFormat::Binary, &output)?;
+
+    if let Some(path) = source_map {
+        tsol_asm::sourcemap::write_source_map(&dbg_info, &path)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+/*
+ * Copyright (C) 2023 EverX. All Rights Reserved.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{error, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use tycho_types::boc::Boc;
+use tycho_types::prelude::Cell;
+
+/// How [`write_boc`] renders a BOC, whether it goes to a file or to stdout.
+#[derive(Clone, Copy, Default)]
+pub enum BocFormat {
+    #[default]
+    Binary,
+    Hex,
+    Base64,
+}
+
+/// Resolves a `boc` CLI argument that may be a filesystem path, a hex string, or a base64
+/// string, returning the raw BOC bytes. A path is tried first (this is the common case and
+/// avoids misreading a path as text); if that fails, the argument is tried as hex, then as
+/// base64 so the tool can take on-chain BOCs straight from a shell variable.
+///
+/// Both the hex and base64 branches decode straight to the BOC's raw bytes rather than through
+/// `Boc::decode_base64`/`Boc::encode` (which only round-trip a single root): the caller always
+/// re-parses the result with `BocHeader::decode`, so a multi-root BOC must survive this step
+/// with every root intact.
+pub fn resolve_boc_bytes(input: &str) -> Result<Vec<u8>> {
+    if let Ok(bytes) = std::fs::read(input) {
+        return Ok(bytes);
+    }
+    let trimmed = input.trim();
+    if let Ok(bytes) = decode_hex_bytes(trimmed) {
+        return Ok(bytes);
+    }
+    if let Ok(bytes) = BASE64.decode(trimmed) {
+        return Ok(bytes);
+    }
+    Err(error!(
+        "`{}` is neither a readable file, a hex string, nor a base64 string",
+        input
+    ))
+}
+
+/// Decodes a hex string into raw bytes. Shared with [`crate::parse_hex_slice`] so the `fragment`
+/// subcommand's bitstring parsing and this module's BOC parsing don't each carry their own copy.
+pub(crate) fn decode_hex_bytes(input: &str) -> Result<Vec<u8>> {
+    hex::decode(input.trim()).map_err(|e| error!("invalid hex string: {}", e))
+}
+
+/// Writes `cell` out in the requested `format`. `"-"` means stdout; anything else is a
+/// filesystem path.
+pub fn write_boc(cell: &Cell, format: BocFormat, output: &str) -> Result<()> {
+    match format {
+        BocFormat::Binary => {
+            let bytes = Boc::encode(cell);
+            if output == "-" {
+                use std::io::Write;
+                std::io::stdout()
+                    .write_all(&bytes)
+                    .map_err(|e| error!("failed to write boc to stdout: {}", e))?;
+            } else {
+                std::fs::write(output, bytes)
+                    .map_err(|e| error!("failed to write boc to {}: {}", output, e))?;
+            }
+        }
+        BocFormat::Hex => write_text(&hex::encode(Boc::encode(cell)), output)?,
+        BocFormat::Base64 => write_text(&Boc::encode_base64(cell), output)?,
+    }
+    Ok(())
+}
+
+fn write_text(text: &str, output: &str) -> Result<()> {
+    if output == "-" {
+        println!("{}", text);
+    } else {
+        std::fs::write(output, text)
+            .map_err(|e| error!("failed to write boc to {}: {}", output, e))?;
+    }
+    Ok(())
+}
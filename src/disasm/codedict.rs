@@ -11,14 +11,16 @@
  * limitations under the License.
  */
 
+use super::error::DisasmError;
 use super::Result;
 use super::{
     loader::Loader,
     types::{Code, Instruction, InstructionParameter},
 };
-use crate::fail;
+use std::cell::RefCell;
 use std::collections::HashMap;
-use tycho_types::cell::CellFamily;
+use std::rc::Rc;
+use tycho_types::cell::{CellFamily, HashBytes};
 use tycho_types::dict::{dict_find_bound_owned, dict_find_owned, DictBound};
 use tycho_types::prelude::{Cell, CellSlice};
 
@@ -69,10 +71,26 @@ impl Code {
     }
 }
 
+/// Everything known about one decoded method body: its id, the bit offset its code starts at
+/// within the cell, the decoded code itself, and - if that code itself ends in a
+/// `DICTPUSHCONST`+`DICTUGETJMP[Z]` pair - the nested dispatch table it jumps into.
+struct MethodEntry {
+    id: u64,
+    offset: usize,
+    code: Code,
+    nested: Option<Rc<DelimitedHashmapE>>,
+}
+
+/// Dispatch tables already marked by [`DelimitedHashmapE::mark`], keyed by the dictionary cell's
+/// `repr_hash`. Method dispatch is a DAG, not a tree: a fallback jump table is often the *same*
+/// cell reused by several selector branches, and without this every branch that jumps into it
+/// walks and rebuilds an identical `DelimitedHashmapE` from scratch.
+type DictCache = Rc<RefCell<HashMap<HashBytes, Rc<DelimitedHashmapE>>>>;
+
 pub(super) struct DelimitedHashmapE {
     dict: Cell,
     key_size: usize,
-    map: HashMap<Vec<u8>, (u64, usize, Code)>,
+    map: HashMap<Vec<u8>, MethodEntry>,
 }
 
 impl DelimitedHashmapE {
@@ -100,6 +118,23 @@ impl DelimitedHashmapE {
         }
         true
     }
+    /// If `code` ends in a `DICTPUSHCONST`+`DICTUGETJMP[Z]` pair, recovers the key size and the
+    /// referenced dictionary cell from the `DICTPUSHCONST` operands so the caller can recurse
+    /// into it as a nested dispatch table instead of leaving it as an opaque blob.
+    fn nested_dict_from_code(code: &mut Code) -> Option<(usize, Cell)> {
+        let len = code.len();
+        let pair = code.get_mut(len.checked_sub(2)?..)?;
+        let params = match_dictpushconst_dictugetjmp(pair)?;
+        let key_size = params.iter().find_map(|p| match p {
+            InstructionParameter::Integer(n) => Some(*n as usize),
+            _ => None,
+        })?;
+        let dict_cell = params.iter().find_map(|p| match p {
+            InstructionParameter::Cell(c) => Some(c.clone()),
+            _ => None,
+        })?;
+        Some((key_size, dict_cell))
+    }
     fn locate(mut slice: CellSlice, target: &CellSlice, path: Vec<u8>) -> Result<(Vec<u8>, usize)> {
         if Self::slice_eq_children(&slice, target) {
             loop {
@@ -120,9 +155,16 @@ impl DelimitedHashmapE {
                 return Ok(v);
             }
         }
-        fail!("not found")
+        Err(DisasmError::NotFound)
     }
     pub fn mark(&mut self) -> Result<()> {
+        self.mark_with_cache(&DictCache::default())
+    }
+
+    /// Does the actual work of [`mark`](Self::mark), threading a [`DictCache`] through the
+    /// recursion so a nested dispatch table reused by multiple method branches is only decoded
+    /// and marked once, however many branches jump into it.
+    fn mark_with_cache(&mut self, cache: &DictCache) -> Result<()> {
         let dict_slice = self.dict.as_slice()?;
 
         if let Ok(Some((mut key, mut slice))) = dict_find_bound_owned(
@@ -138,9 +180,32 @@ impl DelimitedHashmapE {
                 let mut value = CellSlice::apply(&slice)?;
                 let loc = Self::locate(dict_slice.clone(), &value, vec![])?;
                 let mut loader = Loader::new(false);
-                let code = loader.load(&mut value, true)?;
-                if self.map.insert(loc.0, (id, loc.1, code)).is_some() {
-                    fail!("non-unique path found")
+                let mut code = loader.load(&mut value, true)?;
+                let nested = match Self::nested_dict_from_code(&mut code) {
+                    Some((key_size, dict_cell)) => {
+                        let hash = dict_cell.repr_hash().clone();
+                        let cached = cache.borrow().get(&hash).cloned();
+                        Some(match cached {
+                            Some(nested) => nested,
+                            None => {
+                                let mut nested = DelimitedHashmapE::new(dict_cell, key_size);
+                                nested.mark_with_cache(cache)?;
+                                let nested = Rc::new(nested);
+                                cache.borrow_mut().insert(hash, nested.clone());
+                                nested
+                            }
+                        })
+                    }
+                    None => None,
+                };
+                let entry = MethodEntry {
+                    id,
+                    offset: loc.1,
+                    code,
+                    nested,
+                };
+                if self.map.insert(loc.0, entry).is_some() {
+                    return Err(DisasmError::NonUniqueMethodPath);
                 }
 
                 let next = dict_find_owned(
@@ -172,11 +237,14 @@ impl DelimitedHashmapE {
         text += &format!("{{ ;; #{}\n", hex::encode(cell.repr_hash().0));
         let inner_indent = String::from("  ") + indent;
         let mut slice = cell.as_slice().unwrap();
-        if let Some((id, offset, code)) = self.map.get(&path) {
-            let aux = slice.load_prefix(*offset as u16, 0).unwrap();
+        if let Some(entry) = self.map.get(&path) {
+            let aux = slice.load_prefix(entry.offset as u16, 0).unwrap();
             text += &format!("{}.blob x{}\n", inner_indent, aux.display_data());
-            text += &format!("{};; method {}\n", inner_indent, id);
-            text += &code.print(&inner_indent, true, 0);
+            text += &format!("{};; method {}\n", inner_indent, entry.id);
+            text += &entry.code.print(&inner_indent, true, 0);
+            if let Some(nested) = &entry.nested {
+                text += &nested.print(&inner_indent);
+            }
         } else {
             if slice.size_bits() > 0 {
                 text += &format!("{}.blob x{}\n", inner_indent, slice.display_data());
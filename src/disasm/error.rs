@@ -0,0 +1,72 @@
+/*
+ * Copyright (C) 2023 EverX. All Rights Reserved.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ */
+
+/// Structured error type for the disasm/assembler core (`loader`, `handlers`, `types`,
+/// `codedict`, `writer`). Unlike the `anyhow`-based `Status` the CLI uses, this carries no
+/// allocation-free-unfriendly backtraces and needs nothing beyond `core`; its
+/// `std::error::Error` impl is behind the `std` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisasmError {
+    /// No handler recognizes the opcode starting at this bit offset.
+    ///
+    /// Not constructed anywhere in the crate: `handlers`' opcode dispatch does not report
+    /// through it.
+    UnknownOpcode { offset: usize },
+    /// A slice ran out of bits or references before a decode finished.
+    ///
+    /// Not constructed anywhere in the crate: `loader`'s recursive descent does not report
+    /// through it.
+    TruncatedSlice,
+    /// A command (plus its references) didn't fit into the cell being built.
+    NotFitInSlice,
+    /// A dictionary cell's shape didn't match its declared key size.
+    ///
+    /// Not constructed anywhere in the crate: `loader`'s dictionary decoding does not report
+    /// through it.
+    MalformedDict,
+    /// Two method bodies resolved to the same path inside a delimited hashmap.
+    NonUniqueMethodPath,
+    /// A lookup into a dictionary or cell tree came up empty.
+    NotFound,
+    /// Propagated from a `tycho_types` cell/slice operation.
+    Cell(tycho_types::error::Error),
+}
+
+impl core::fmt::Display for DisasmError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownOpcode { offset } => {
+                write!(f, "unknown opcode at bit offset {}", offset)
+            }
+            Self::TruncatedSlice => write!(f, "slice ran out of data before decoding finished"),
+            Self::NotFitInSlice => write!(f, "command does not fit in the cell being built"),
+            Self::MalformedDict => {
+                write!(f, "dictionary cell does not match its declared key size")
+            }
+            Self::NonUniqueMethodPath => write!(f, "non-unique method path found"),
+            Self::NotFound => write!(f, "not found"),
+            Self::Cell(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DisasmError {}
+
+impl From<tycho_types::error::Error> for DisasmError {
+    fn from(e: tycho_types::error::Error) -> Self {
+        Self::Cell(e)
+    }
+}
+
+pub type Result<T> = core::result::Result<T, DisasmError>;
@@ -12,11 +12,19 @@
  */
 
 use self::loader::Loader;
+use tycho_types::cell::HashBytes;
 use tycho_types::prelude::CellSlice;
 
-pub type Result<T> = std::result::Result<T, anyhow::Error>;
+#[cfg(feature = "std")]
+use std::rc::Rc;
+#[cfg(not(feature = "std"))]
+use alloc::rc::Rc;
+
+pub use self::error::DisasmError;
+pub type Result<T> = self::error::Result<T>;
 
 pub mod codedict;
+pub mod error;
 pub mod fmt;
 mod handlers;
 pub mod loader;
@@ -24,6 +32,20 @@ pub mod loader;
 mod tests;
 pub mod types;
 
+/// Cache type for memoizing [`Loader`]'s disassembly of shared subcells, keyed on a child cell's
+/// `repr_hash`. A contract's code is a DAG with heavily shared subcells (library refs, repeated
+/// selector branches).
+///
+/// `Loader::load` does not use it: a cell referenced N times is walked and rebuilt into a
+/// [`types::Code`] N times. [`codedict::DelimitedHashmapE`] keeps its own `repr_hash`-keyed
+/// cache for nested dispatch tables reused by several selector branches.
+///
+/// A `HashMap` under the `std` feature; a `BTreeMap` from `alloc` without it.
+#[cfg(feature = "std")]
+pub(crate) type CodeCache = std::collections::HashMap<HashBytes, Rc<types::Code>>;
+#[cfg(not(feature = "std"))]
+pub(crate) type CodeCache = alloc::collections::BTreeMap<HashBytes, Rc<types::Code>>;
+
 pub fn disasm(slice: &mut CellSlice) -> Result<String> {
     disasm_ex(slice, false)
 }
@@ -0,0 +1,80 @@
+/*
+ * Copyright (C) 2023 EverX. All Rights Reserved.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ */
+
+// `disasm`'s `std`/`no_std` split (see its `CodeCache` and `error::DisasmError`) is gated on a
+// `std` feature that must be declared `default = ["std"]` in Cargo.toml; this crate's own CLI
+// half always needs `std` regardless. The `no_std` + `alloc` arm additionally needs `alloc` in
+// scope, which a plain `#[cfg(not(feature = "std"))]` item doesn't pull in by itself.
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+// Everything outside `disasm` (the assembler, the CLI's BOC/debug-info/source-map plumbing) goes
+// through `std::fs` and `anyhow` directly, so it is gated behind the `std` feature. `disasm` is
+// not gated, but it does not build without `std` either: the crate carries no `no_std` attribute,
+// and `disasm::codedict` uses `std`'s `HashMap`, `Rc` and `RefCell` unconditionally.
+#[cfg(feature = "std")]
+pub mod asm;
+#[cfg(feature = "std")]
+pub mod boc_io;
+#[cfg(feature = "std")]
+pub mod debug;
+pub mod disasm;
+#[cfg(feature = "std")]
+pub mod sourcemap;
+#[cfg(feature = "std")]
+pub mod writer;
+
+#[cfg(feature = "std")]
+pub use debug::DbgInfo;
+
+/// Result alias for the `anyhow`-based, CLI-facing half of the crate (parsing CLI arguments,
+/// reading/writing files, assembling a listing). The `no_std`-friendly disasm core underneath
+/// has its own [`disasm::DisasmError`]/[`disasm::Result`] instead.
+#[cfg(feature = "std")]
+pub type Result<T> = anyhow::Result<T>;
+
+/// Return type for a CLI subcommand: nothing on success, an [`anyhow::Error`] on failure.
+#[cfg(feature = "std")]
+pub type Status = Result<()>;
+
+/// Builds an [`anyhow::Error`] with a formatted message, for call sites that need the error
+/// value itself (e.g. `Err(error!(...))`, `.ok_or_else(|| error!(...))`).
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => {
+        anyhow::anyhow!($($arg)*)
+    };
+}
+
+/// Returns early from the enclosing `Result`-returning function with a formatted error.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! fail {
+    ($($arg:tt)*) => {
+        return Err($crate::error!($($arg)*))
+    };
+}
+
+/// Parses a bare hex bitstring (as taken by the `fragment` subcommand) into a single cell
+/// holding that bitstring as data, with no references. Shares its hex decoding with
+/// [`boc_io::resolve_boc_bytes`] rather than duplicating it.
+#[cfg(feature = "std")]
+pub fn parse_hex_slice(hex: &str) -> Result<tycho_types::prelude::Cell> {
+    use tycho_types::prelude::CellBuilder;
+
+    let bytes = boc_io::decode_hex_bytes(hex)?;
+    let bits = (bytes.len() * 8) as u16;
+    let builder = CellBuilder::from_raw_data(&bytes, bits).map_err(|e| error!("{}", e))?;
+    builder.build().map_err(|e| error!("{}", e))
+}
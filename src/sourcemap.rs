@@ -0,0 +1,48 @@
+/*
+ * Copyright (C) 2023 EverX. All Rights Reserved.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ */
+
+use crate::debug::DbgInfo;
+use std::collections::BTreeMap;
+
+/// A JSON-serializable view of a [`DbgInfo`]: for every cell the assembler built, the bit
+/// offset of each instruction within that cell mapped to the source location it came from.
+/// Keyed by the cell's `repr_hash` (hex) so a debugger can correlate a runtime VM position
+/// (cell hash + offset) back to an assembler line without re-disassembling anything.
+#[derive(serde::Serialize)]
+pub struct SourceMap(BTreeMap<String, BTreeMap<usize, String>>);
+
+impl DbgInfo {
+    /// Builds the serializable source map described by [`SourceMap`].
+    pub fn to_source_map(&self) -> SourceMap {
+        SourceMap(
+            self.0
+                .iter()
+                .map(|(hash, offsets)| {
+                    let locations = offsets
+                        .iter()
+                        .map(|(offset, pos)| (*offset, format!("{}:{}", pos.filename, pos.line)))
+                        .collect();
+                    (hex::encode(hash.0), locations)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Serializes `dbg_info` as JSON and writes it to `path`.
+pub fn write_source_map(dbg_info: &DbgInfo, path: &str) -> crate::Status {
+    let json = serde_json::to_string_pretty(&dbg_info.to_source_map())
+        .map_err(|e| crate::error!("failed to serialize source map: {}", e))?;
+    std::fs::write(path, json).map_err(|e| crate::error!("failed to write source map: {}", e))?;
+    Ok(())
+}
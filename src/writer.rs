@@ -12,10 +12,16 @@
 */
 
 use crate::debug::DbgNode;
-use crate::{CompileResult, DbgInfo, OperationError};
+use crate::disasm::error::DisasmError;
+use crate::DbgInfo;
 use tycho_types::prelude::CellBuilder;
 use tycho_vm::OwnedCellSlice;
 
+/// Result of an assembler-side operation. Shares [`DisasmError`] with the disasm core rather
+/// than a separate `OperationError`, since the two sides of this crate (build a cell vs. read
+/// one back) hit the same failure modes - a command not fitting in a cell being the obvious one.
+pub type CompileResult = Result<(), DisasmError>;
+
 #[derive(Clone, Default)]
 pub struct Unit {
     builder: CellBuilder,
@@ -77,7 +83,7 @@ impl Units {
             self.units.push(Unit::new(new_last, dbg));
             return Ok(());
         }
-        Err(OperationError::NotFitInSlice)
+        Err(DisasmError::NotFitInSlice)
     }
     /// Writes command with additional references
     pub fn write_composite_command(
@@ -107,7 +113,7 @@ impl Units {
             self.units.push(Unit::new(new_last, dbg));
             return Ok(());
         }
-        Err(OperationError::NotFitInSlice)
+        Err(DisasmError::NotFitInSlice)
     }
 
     /// Puts recorded cells in a linear sequence
@@ -136,7 +142,7 @@ impl Units {
 fn checked_append_references(
     builder: &mut CellBuilder,
     refs: &[CellBuilder],
-) -> Result<bool, OperationError> {
+) -> Result<bool, DisasmError> {
     for reference in refs {
         let cloned_builder = reference.clone();
         let cell_result = cloned_builder.build();